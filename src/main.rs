@@ -9,6 +9,7 @@ mod utils;
 use models::args::MainArgs;
 use models::config::Config;
 use models::manifest::Manifest;
+use steps::fetch::Fetcher;
 use steps::generate::Generator;
 use steps::package::Packaging;
 use steps::prepare::Preparator;
@@ -49,6 +50,12 @@ fn main() -> Result<()> {
     info!(" - Previous versions dir: {}", &conf.env.previous_dir.display());
     info!(" - Output dir: {}", &conf.env.output_dir.display());
 
+    if conf.fetch.enabled && !args.packaging_only {
+        info!("Fetching previous versions for delta generation...");
+        let fetcher = Fetcher::init(&conf);
+        fetcher.run().context("Fetching previous versions failed")?;
+    }
+
     if !args.updater_data_only {
         let prep = Preparator::init(&conf);
         prep.run().context("Preparation failed")?;
@@ -96,6 +103,22 @@ fn main() -> Result<()> {
         }
     }
 
+    if !conf.package.checksums.skip {
+        info!("Writing checksum manifest for all output artifacts...");
+        let sums_files =
+            steps::checksums::write_checksums(&conf).context("Writing checksum manifest failed")?;
+
+        if conf.package.checksums.sign {
+            info!("Signing checksum manifest...");
+            let mut signer = Signer::init(conf.package.updater.private_key.as_ref());
+            for sums_file in &sums_files {
+                signer
+                    .sign_file(sums_file)
+                    .context("Signing checksum manifest failed")?;
+            }
+        }
+    }
+
     if !args.updater_data_only && conf.post.copy_to_old {
         info!("Copying install dir and PDBs to backup directory...");
         steps::post::copy_to_old(&conf).context("Copying files failed")?;
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// Command line arguments for bouf.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct MainArgs {
+    /// Path to the TOML configuration file
+    #[arg(short, long, default_value = "bouf.toml")]
+    pub config: PathBuf,
+
+    /// Only validate the config and exit
+    #[arg(long)]
+    pub test_config: bool,
+
+    /// Enable verbose (trace) logging
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Skip fetching and manifest/patch generation; only run preparation and packaging steps
+    #[arg(long)]
+    pub packaging_only: bool,
+
+    /// Only generate updater data (manifest + patches), skip installer/zip creation
+    #[arg(long)]
+    pub updater_data_only: bool,
+
+    /// Skip binary patch generation, only hash files for the manifest
+    #[arg(long)]
+    pub skip_patches: bool,
+
+    /// Report live progress while preparing, diffing and packaging
+    #[arg(long)]
+    pub progress: bool,
+}
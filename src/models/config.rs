@@ -0,0 +1,250 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::args::MainArgs;
+
+/// Top level configuration, loaded from a TOML file and then patched up
+/// with whatever overrides were passed on the command line.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Config {
+    pub env: EnvConfig,
+    #[serde(default)]
+    pub general: GeneralConfig,
+    #[serde(default)]
+    pub fetch: FetchConfig,
+    #[serde(default)]
+    pub prepare: PrepareConfig,
+    #[serde(default)]
+    pub package: PackageConfig,
+    #[serde(default)]
+    pub post: PostConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct EnvConfig {
+    pub input_dir: PathBuf,
+    pub previous_dir: PathBuf,
+    pub output_dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeneralConfig {
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// Number of worker threads to use for delta/patch generation. Defaults
+    /// to the detected CPU count when unset.
+    #[serde(default)]
+    pub threads: Option<usize>,
+    /// Report live progress (bars on a terminal, throttled "N/M done" lines
+    /// otherwise) while preparing, diffing and packaging.
+    #[serde(default)]
+    pub progress: bool,
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            log_level: default_log_level(),
+            threads: None,
+            progress: false,
+        }
+    }
+}
+
+/// Automatic fetching of previous release archives for delta generation.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FetchConfig {
+    /// Enable the fetch step. When disabled, `previous_dir` must already be
+    /// populated by hand.
+    #[serde(default)]
+    pub enabled: bool,
+    /// URL of a JSON document listing prior versions, their archive URLs
+    /// and SHA-256 digests.
+    #[serde(default)]
+    pub index_url: String,
+    /// How many of the most recent prior versions to fetch.
+    #[serde(default = "default_fetch_count")]
+    pub count: usize,
+    /// Local directory used to cache downloaded archives across runs.
+    #[serde(default = "default_cache_dir")]
+    pub cache_dir: PathBuf,
+}
+
+fn default_fetch_count() -> usize {
+    3
+}
+
+fn default_cache_dir() -> PathBuf {
+    PathBuf::from(".bouf-cache")
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            index_url: String::new(),
+            count: default_fetch_count(),
+            cache_dir: default_cache_dir(),
+        }
+    }
+}
+
+/// Gitignore-style file selection applied while staging the input directory.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PrepareConfig {
+    /// Glob patterns (gitignore syntax) of files to include. Empty means
+    /// "include everything not excluded".
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns (gitignore syntax) of files to exclude.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PackageConfig {
+    #[serde(default)]
+    pub zip: ZipConfig,
+    #[serde(default)]
+    pub installer: InstallerConfig,
+    #[serde(default)]
+    pub updater: UpdaterConfig,
+    #[serde(default)]
+    pub checksums: ChecksumsConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ZipConfig {
+    #[serde(default)]
+    pub skip: bool,
+    #[serde(default)]
+    pub compression: CompressionConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct InstallerConfig {
+    #[serde(default)]
+    pub skip: bool,
+    #[serde(default)]
+    pub compression: NsisCompressionConfig,
+}
+
+/// Compression settings for the ZIP archives. Defaults match the previous
+/// hardcoded behavior (Deflate at the library default level).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    #[serde(default)]
+    pub method: ZipCompressionMethod,
+    /// Compression level, 0-22. Meaning depends on `method`; `None` uses
+    /// that method's default.
+    #[serde(default)]
+    pub level: Option<i32>,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            method: ZipCompressionMethod::default(),
+            level: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ZipCompressionMethod {
+    Store,
+    #[default]
+    Deflate,
+    Zstd,
+}
+
+/// Compression settings passed through to the NSIS installer script.
+/// Defaults match NSIS's own default (`lzma`).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NsisCompressionConfig {
+    #[serde(default)]
+    pub method: NsisCompressionMethod,
+}
+
+impl Default for NsisCompressionConfig {
+    fn default() -> Self {
+        Self {
+            method: NsisCompressionMethod::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum NsisCompressionMethod {
+    Zlib,
+    Bzip2,
+    #[default]
+    Lzma,
+}
+
+/// A `SHA256SUMS`-style manifest covering every produced artifact, signed
+/// only when `sign` is set.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ChecksumsConfig {
+    #[serde(default)]
+    pub skip: bool,
+    /// Also emit a `BLAKE3SUMS` file alongside `SHA256SUMS`.
+    #[serde(default)]
+    pub blake3: bool,
+    /// Whether the sums file(s) should be signed, same as the updater
+    /// manifest.
+    #[serde(default)]
+    pub sign: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct UpdaterConfig {
+    #[serde(default)]
+    pub skip_sign: bool,
+    pub private_key: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PostConfig {
+    #[serde(default)]
+    pub copy_to_old: bool,
+}
+
+impl Config {
+    /// Load configuration from a TOML file on disk.
+    pub fn from_file(path: &std::path::Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        let conf: Config = toml::from_str(&data)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        Ok(conf)
+    }
+
+    /// Apply command line overrides and validate the resulting config.
+    pub fn apply_args(&mut self, args: &MainArgs) -> Result<()> {
+        if args.verbose {
+            self.general.log_level = "trace".to_string();
+        }
+        if args.progress {
+            self.general.progress = true;
+        }
+
+        if !self.env.input_dir.exists() {
+            anyhow::bail!("Input dir does not exist: {}", self.env.input_dir.display());
+        }
+        if !self.env.output_dir.exists() {
+            std::fs::create_dir_all(&self.env.output_dir)
+                .context("Failed to create output dir")?;
+        }
+
+        Ok(())
+    }
+}
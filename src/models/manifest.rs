@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+/// A single file entry in the update manifest, along with the patches
+/// (if any) that can be applied to previous versions to produce it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path of the file relative to the install root, using forward slashes.
+    pub path: String,
+    /// SHA-256 of the current version of the file.
+    pub hash: String,
+    /// Size of the current version of the file, in bytes.
+    pub size: u64,
+    /// Patches that can be applied to older versions of this file.
+    #[serde(default)]
+    pub patches: Vec<PatchEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchEntry {
+    /// Version this patch can be applied to.
+    pub from_version: String,
+    /// SHA-256 of the previous version's file this patch was generated from.
+    pub from_hash: String,
+    /// Filename of the patch, relative to the patch output directory.
+    pub filename: String,
+    /// Size of the patch file, in bytes.
+    pub size: u64,
+}
+
+/// The full update manifest for a release.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Manifest {
+    pub version: String,
+    pub files: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Sort entries by path so the serialized manifest is deterministic
+    /// regardless of the order in which files were processed.
+    pub fn sort(&mut self) {
+        self.files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str) -> ManifestEntry {
+        ManifestEntry {
+            path: path.to_string(),
+            hash: String::new(),
+            size: 0,
+            patches: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn sort_orders_entries_by_path_regardless_of_insertion_order() {
+        let mut manifest = Manifest::new("1.0.0");
+        manifest.files = vec![entry("z/file.bin"), entry("a/file.bin"), entry("m/file.bin")];
+
+        manifest.sort();
+
+        let paths: Vec<_> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a/file.bin", "m/file.bin", "z/file.bin"]);
+    }
+}
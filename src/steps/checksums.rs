@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use log::info;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::models::config::Config;
+
+const SHA256_SUMS_FILENAME: &str = "SHA256SUMS";
+const BLAKE3_SUMS_FILENAME: &str = "BLAKE3SUMS";
+
+/// Compute digests of every artifact in `output_dir` and write them to
+/// `SHA256SUMS` (and, if `package.checksums.blake3` is set, an additional
+/// `BLAKE3SUMS`), independent of the updater manifest signature. Returns the
+/// path to each sums file that was written.
+pub fn write_checksums(conf: &Config) -> Result<Vec<PathBuf>> {
+    let want_blake3 = conf.package.checksums.blake3;
+    let skip_names = [SHA256_SUMS_FILENAME, BLAKE3_SUMS_FILENAME];
+
+    let mut sha256_lines = Vec::new();
+    let mut blake3_lines = Vec::new();
+
+    for entry in WalkDir::new(&conf.env.output_dir) {
+        let entry = entry.context("Failed to walk output directory")?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(&conf.env.output_dir)
+            .expect("walked entry must be under output_dir");
+        if skip_names.iter().any(|name| rel == std::path::Path::new(name)) {
+            continue;
+        }
+        let rel_display = rel.to_string_lossy().replace('\\', "/");
+
+        let data = std::fs::read(entry.path())
+            .with_context(|| format!("Failed to read artifact: {rel_display}"))?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&data);
+        sha256_lines.push(format!("{:x}  {rel_display}", hasher.finalize()));
+
+        if want_blake3 {
+            blake3_lines.push(format!("{}  {rel_display}", blake3::hash(&data).to_hex()));
+        }
+    }
+    sha256_lines.sort();
+    blake3_lines.sort();
+
+    let mut written = Vec::new();
+    written.push(write_sums_file(conf, SHA256_SUMS_FILENAME, &sha256_lines)?);
+    if want_blake3 {
+        written.push(write_sums_file(conf, BLAKE3_SUMS_FILENAME, &blake3_lines)?);
+    }
+
+    Ok(written)
+}
+
+fn write_sums_file(conf: &Config, filename: &str, lines: &[String]) -> Result<PathBuf> {
+    let path = conf.env.output_dir.join(filename);
+    std::fs::write(&path, format!("{}\n", lines.join("\n")))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    info!("Wrote {}", path.display());
+    Ok(path)
+}
@@ -0,0 +1,276 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info};
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::models::config::Config;
+
+/// A single entry in the remote release index.
+#[derive(Debug, Deserialize)]
+struct ReleaseIndexEntry {
+    version: String,
+    url: String,
+    sha256: String,
+}
+
+/// Downloads and unpacks previous release archives into `previous_dir` so
+/// `Generator` has something to diff against, without requiring maintainers
+/// to stage them by hand.
+pub struct Fetcher<'a> {
+    conf: &'a Config,
+}
+
+impl<'a> Fetcher<'a> {
+    pub fn init(conf: &'a Config) -> Self {
+        Self { conf }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let fetch_conf = &self.conf.fetch;
+
+        info!("Fetching release index from {}", fetch_conf.index_url);
+        let mut index: Vec<ReleaseIndexEntry> = reqwest::blocking::get(&fetch_conf.index_url)
+            .and_then(|resp| resp.error_for_status())
+            .context("Failed to download release index")?
+            .json()
+            .context("Failed to parse release index as JSON")?;
+
+        Self::sort_newest_first(&mut index);
+
+        std::fs::create_dir_all(&fetch_conf.cache_dir).context("Failed to create cache dir")?;
+        std::fs::create_dir_all(&self.conf.env.previous_dir)
+            .context("Failed to create previous_dir")?;
+
+        for entry in index.into_iter().take(fetch_conf.count) {
+            self.fetch_one(&entry)
+                .with_context(|| format!("Failed to fetch previous version {}", entry.version))?;
+        }
+
+        Ok(())
+    }
+
+    fn fetch_one(&self, entry: &ReleaseIndexEntry) -> Result<()> {
+        let dest_dir = self.conf.env.previous_dir.join(&entry.version);
+        if dest_dir.exists() {
+            debug!("{} already present in previous_dir, skipping", entry.version);
+            return Ok(());
+        }
+
+        let archive_path = self.cached_archive_path(entry);
+        let expected = entry.sha256.to_lowercase();
+
+        if Self::verify_cached(&archive_path, &expected)? {
+            debug!("Using cached archive for {}", entry.version);
+        } else {
+            self.download(&entry.url, &archive_path)?;
+            Self::verify_downloaded(&archive_path, &expected, &entry.version)?;
+        }
+
+        self.unpack(&archive_path, &dest_dir)
+    }
+
+    /// Check a (possibly stale) cache entry against the expected digest. A
+    /// cache hit that no longer verifies is discarded rather than reused, so
+    /// a previously-corrupted download doesn't poison every future run.
+    fn verify_cached(path: &Path, expected: &str) -> Result<bool> {
+        if !path.exists() {
+            return Ok(false);
+        }
+        if Self::hash_file(path)? == expected {
+            return Ok(true);
+        }
+        debug!("Cached archive {} failed verification, discarding", path.display());
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove corrupt cache entry: {}", path.display()))?;
+        Ok(false)
+    }
+
+    /// Verify a freshly-downloaded archive against the expected digest,
+    /// removing it on mismatch so it isn't reused as a "cached" copy by a
+    /// later run.
+    fn verify_downloaded(path: &Path, expected: &str, version: &str) -> Result<()> {
+        let digest = Self::hash_file(path)?;
+        if digest != expected {
+            let _ = std::fs::remove_file(path);
+            bail!("Digest mismatch for {version}: expected {expected}, got {digest}");
+        }
+        Ok(())
+    }
+
+    /// Don't trust the index to already be ordered newest-first: sort by
+    /// parsed semver, falling back to a plain string compare for entries
+    /// that don't parse so one odd entry doesn't fail the whole run.
+    fn sort_newest_first(index: &mut [ReleaseIndexEntry]) {
+        index.sort_by(|a, b| match (Version::parse(&a.version), Version::parse(&b.version)) {
+            (Ok(a), Ok(b)) => b.cmp(&a),
+            _ => b.version.cmp(&a.version),
+        });
+    }
+
+    fn cached_archive_path(&self, entry: &ReleaseIndexEntry) -> PathBuf {
+        self.conf
+            .fetch
+            .cache_dir
+            .join(format!("{}-{}.zip", entry.version, entry.sha256))
+    }
+
+    fn download(&self, url: &str, dest: &Path) -> Result<()> {
+        info!("Downloading {url}");
+        let mut resp = reqwest::blocking::get(url)
+            .and_then(|resp| resp.error_for_status())
+            .with_context(|| format!("Failed to download {url}"))?;
+
+        let tmp_path = dest.with_extension("part");
+        {
+            let mut file = File::create(&tmp_path)
+                .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+            resp.copy_to(&mut file)
+                .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+            file.flush()?;
+        }
+        std::fs::rename(&tmp_path, dest)
+            .with_context(|| format!("Failed to move downloaded archive to {}", dest.display()))?;
+
+        Ok(())
+    }
+
+    fn unpack(&self, archive_path: &Path, dest_dir: &Path) -> Result<()> {
+        let file = File::open(archive_path)
+            .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
+        let mut zip = zip::ZipArchive::new(file)
+            .with_context(|| format!("Failed to read archive: {}", archive_path.display()))?;
+
+        std::fs::create_dir_all(dest_dir)
+            .with_context(|| format!("Failed to create {}", dest_dir.display()))?;
+        zip.extract(dest_dir)
+            .with_context(|| format!("Failed to extract archive into {}", dest_dir.display()))?;
+
+        Ok(())
+    }
+
+    fn hash_file(path: &Path) -> Result<String> {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut hasher = Sha256::new();
+        hasher.update(&data);
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::config::{EnvConfig, FetchConfig};
+
+    fn test_conf(tmp: &std::path::Path) -> Config {
+        Config {
+            env: EnvConfig {
+                input_dir: tmp.to_path_buf(),
+                previous_dir: tmp.join("previous"),
+                output_dir: tmp.join("output"),
+            },
+            fetch: FetchConfig {
+                enabled: true,
+                index_url: String::new(),
+                count: 1,
+                cache_dir: tmp.join("cache"),
+            },
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn verify_downloaded_fails_loudly_on_digest_mismatch_and_removes_the_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("1.2.3-deadbeef.zip");
+        std::fs::write(&archive_path, b"not actually a zip, and wrong hash").unwrap();
+        let expected = "0".repeat(64);
+
+        let err = Fetcher::verify_downloaded(&archive_path, &expected, "1.2.3").unwrap_err();
+
+        assert!(format!("{err}").contains("Digest mismatch"));
+        assert!(
+            !archive_path.exists(),
+            "a freshly-downloaded file that fails verification must not be left behind to poison the cache"
+        );
+    }
+
+    #[test]
+    fn verify_cached_discards_a_poisoned_cache_entry_instead_of_reusing_it() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("1.2.3-deadbeef.zip");
+        std::fs::write(&archive_path, b"corrupted from a previous failed run").unwrap();
+        let expected = "0".repeat(64);
+
+        let is_valid = Fetcher::verify_cached(&archive_path, &expected).unwrap();
+
+        assert!(!is_valid);
+        assert!(
+            !archive_path.exists(),
+            "a cache entry that fails verification must be deleted so the next run re-downloads it"
+        );
+    }
+
+    #[test]
+    fn verify_cached_accepts_a_matching_entry_without_touching_it() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("1.2.3-abc.zip");
+        std::fs::write(&archive_path, b"good archive bytes").unwrap();
+        let expected = Fetcher::hash_file(&archive_path).unwrap();
+
+        let is_valid = Fetcher::verify_cached(&archive_path, &expected).unwrap();
+
+        assert!(is_valid);
+        assert!(archive_path.exists());
+    }
+
+    #[test]
+    fn fetch_one_skips_already_unpacked_versions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let conf = test_conf(tmp.path());
+        std::fs::create_dir_all(&conf.env.previous_dir).unwrap();
+
+        let entry = ReleaseIndexEntry {
+            version: "1.2.3".to_string(),
+            url: "https://example.invalid/1.2.3.zip".to_string(),
+            sha256: "0".repeat(64),
+        };
+        std::fs::create_dir_all(conf.env.previous_dir.join(&entry.version)).unwrap();
+
+        // No archive present anywhere and no network access in this test, so
+        // a network fetch attempt would fail the test; reaching `Ok(())`
+        // proves the already-unpacked shortcut was taken.
+        Fetcher::init(&conf).fetch_one(&entry).unwrap();
+    }
+
+    #[test]
+    fn index_is_sorted_newest_first_regardless_of_input_order() {
+        let mut index = vec![
+            ReleaseIndexEntry {
+                version: "1.0.0".to_string(),
+                url: String::new(),
+                sha256: String::new(),
+            },
+            ReleaseIndexEntry {
+                version: "2.5.0".to_string(),
+                url: String::new(),
+                sha256: String::new(),
+            },
+            ReleaseIndexEntry {
+                version: "2.4.9".to_string(),
+                url: String::new(),
+                sha256: String::new(),
+            },
+        ];
+
+        Fetcher::sort_newest_first(&mut index);
+
+        let versions: Vec<_> = index.iter().map(|e| e.version.as_str()).collect();
+        assert_eq!(versions, vec!["2.5.0", "2.4.9", "1.0.0"]);
+    }
+}
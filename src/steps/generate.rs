@@ -0,0 +1,300 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use log::{debug, info};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::models::config::Config;
+use crate::models::manifest::{Manifest, ManifestEntry, PatchEntry};
+use crate::utils::delta;
+use crate::utils::progress::Progress;
+
+/// One (old, new) file pair to be diffed against a single previous version.
+struct DiffJob {
+    rel_path: String,
+    old_path: PathBuf,
+    new_path: PathBuf,
+    from_version: String,
+}
+
+/// Builds the update manifest and, optionally, binary patches against every
+/// version found in `conf.env.previous_dir`.
+pub struct Generator<'a> {
+    conf: &'a Config,
+    create_patches: bool,
+}
+
+impl<'a> Generator<'a> {
+    pub fn init(conf: &'a Config, create_patches: bool) -> Self {
+        Self { conf, create_patches }
+    }
+
+    /// Build the manifest, diffing against every previous version found in
+    /// `conf.env.previous_dir` unless `skip_patches` is set.
+    pub fn run(&self, skip_patches: bool) -> Result<Manifest> {
+        self.configure_thread_pool()?;
+
+        let new_files = self.collect_new_files()?;
+        let version = self
+            .conf
+            .env
+            .output_dir
+            .file_name()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut manifest = Manifest::new(version);
+
+        if self.create_patches && !skip_patches {
+            let jobs = self.collect_diff_jobs(&new_files)?;
+            manifest.files = self.run_diff_jobs(jobs)?;
+        } else {
+            manifest.files = new_files
+                .into_par_iter()
+                .map(|(rel_path, path)| self.hash_entry(&rel_path, &path))
+                .collect::<Result<Vec<_>>>()?;
+        }
+
+        // Ensure deterministic output regardless of the order jobs finished in.
+        manifest.sort();
+        Ok(manifest)
+    }
+
+    /// Configure the global rayon thread pool from `general.threads`,
+    /// defaulting to the number of logical CPUs.
+    fn configure_thread_pool(&self) -> Result<()> {
+        let threads = self.conf.general.threads.unwrap_or_else(num_cpus::get);
+        info!("Using {threads} worker thread(s) for patch generation");
+
+        // Building a new global pool fails if one was already built (e.g. in
+        // tests that call `run` more than once); that's not fatal.
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global();
+
+        Ok(())
+    }
+
+    fn collect_new_files(&self) -> Result<Vec<(String, PathBuf)>> {
+        let mut files = Vec::new();
+        for entry in WalkDir::new(&self.conf.env.output_dir) {
+            let entry = entry.context("Failed to walk output directory")?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(&self.conf.env.output_dir)
+                .expect("walked entry must be under output_dir")
+                .to_string_lossy()
+                .replace('\\', "/");
+            files.push((rel, entry.path().to_path_buf()));
+        }
+        Ok(files)
+    }
+
+    fn collect_diff_jobs(&self, new_files: &[(String, PathBuf)]) -> Result<Vec<DiffJob>> {
+        let mut jobs = Vec::new();
+
+        for prev_entry in std::fs::read_dir(&self.conf.env.previous_dir)
+            .context("Failed to read previous_dir")?
+        {
+            let prev_entry = prev_entry.context("Failed to read previous_dir entry")?;
+            if !prev_entry.file_type()?.is_dir() {
+                continue;
+            }
+            let from_version = prev_entry.file_name().to_string_lossy().into_owned();
+            let prev_dir = prev_entry.path();
+
+            for (rel_path, new_path) in new_files {
+                let old_path = prev_dir.join(rel_path);
+                if old_path.is_file() {
+                    jobs.push(DiffJob {
+                        rel_path: rel_path.clone(),
+                        old_path,
+                        new_path: new_path.clone(),
+                        from_version: from_version.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    /// Run every diff job in parallel, merging the resulting patches into
+    /// one `ManifestEntry` per relative path.
+    fn run_diff_jobs(&self, jobs: Vec<DiffJob>) -> Result<Vec<ManifestEntry>> {
+        use std::collections::BTreeMap;
+
+        let patches_dir = self.conf.env.output_dir.join("patches");
+        std::fs::create_dir_all(&patches_dir).context("Failed to create patches dir")?;
+
+        let progress = Progress::new(self.conf.general.progress, jobs.len() as u64, "Generating patches");
+        let results: Vec<(String, PatchEntry)> = jobs
+            .into_par_iter()
+            .map(|job| {
+                let result = self.diff_one(&job, &patches_dir);
+                progress.inc(1);
+                result
+            })
+            .collect::<Result<Vec<_>>>()?;
+        progress.finish();
+
+        let mut by_path: BTreeMap<String, Vec<PatchEntry>> = BTreeMap::new();
+        for (rel_path, patch) in results {
+            by_path.entry(rel_path).or_default().push(patch);
+        }
+
+        let new_files = self.collect_new_files()?;
+        let mut entries = Vec::with_capacity(new_files.len());
+        for (rel_path, path) in new_files {
+            let mut entry = self.hash_entry(&rel_path, &path)?;
+            if let Some(mut patches) = by_path.remove(&rel_path) {
+                patches.sort_by(|a, b| a.from_version.cmp(&b.from_version));
+                entry.patches = patches;
+            }
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    /// Diff a single (old, new) file pair and write the resulting patch to
+    /// disk. Errors are annotated with the offending relative path so a
+    /// single bad file surfaces clearly instead of being swallowed by the
+    /// thread pool.
+    fn diff_one(&self, job: &DiffJob, patches_dir: &Path) -> Result<(String, PatchEntry)> {
+        let from_hash = Self::hash_file(&job.old_path)
+            .with_context(|| format!("Failed to hash old file: {}", job.rel_path))?;
+
+        let old_data = std::fs::read(&job.old_path)
+            .with_context(|| format!("Failed to read old file: {}", job.rel_path))?;
+        let new_data = std::fs::read(&job.new_path)
+            .with_context(|| format!("Failed to read new file: {}", job.rel_path))?;
+
+        let patch_name = format!(
+            "{}_{}_{}.patch",
+            job.rel_path.replace(['/', '\\'], "_"),
+            job.from_version,
+            &from_hash[..12]
+        );
+        let patch_path = patches_dir.join(&patch_name);
+
+        debug!("Diffing {} (from {})", job.rel_path, job.from_version);
+        let patch_data = delta::diff(&old_data, &new_data)
+            .with_context(|| format!("Failed to diff file: {}", job.rel_path))?;
+        std::fs::write(&patch_path, &patch_data)
+            .with_context(|| format!("Failed to write patch: {}", patch_path.display()))?;
+
+        Ok((
+            job.rel_path.clone(),
+            PatchEntry {
+                from_version: job.from_version.clone(),
+                from_hash,
+                filename: patch_name,
+                size: patch_data.len() as u64,
+            },
+        ))
+    }
+
+    fn hash_entry(&self, rel_path: &str, path: &Path) -> Result<ManifestEntry> {
+        let hash = Self::hash_file(path)
+            .with_context(|| format!("Failed to hash file: {rel_path}"))?;
+        let size = std::fs::metadata(path)
+            .with_context(|| format!("Failed to stat file: {rel_path}"))?
+            .len();
+
+        Ok(ManifestEntry {
+            path: rel_path.to_string(),
+            hash,
+            size,
+            patches: Vec::new(),
+        })
+    }
+
+    fn hash_file(path: &Path) -> Result<String> {
+        let data = std::fs::read(path)?;
+        let mut hasher = Sha256::new();
+        hasher.update(&data);
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::config::EnvConfig;
+
+    fn test_conf(env: EnvConfig) -> Config {
+        Config {
+            env,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn diff_one_error_includes_relative_path_of_offending_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let conf = test_conf(EnvConfig {
+            input_dir: tmp.path().to_path_buf(),
+            previous_dir: tmp.path().join("previous"),
+            output_dir: tmp.path().join("output"),
+        });
+        let generator = Generator::init(&conf, true);
+
+        let job = DiffJob {
+            rel_path: "missing/file.bin".to_string(),
+            old_path: tmp.path().join("does-not-exist.bin"),
+            new_path: tmp.path().join("also-does-not-exist.bin"),
+            from_version: "1.0.0".to_string(),
+        };
+
+        let err = generator.diff_one(&job, tmp.path()).unwrap_err();
+        let message = format!("{err:#}");
+        assert!(
+            message.contains("missing/file.bin"),
+            "error should mention the offending relative path, got: {message}"
+        );
+    }
+
+    #[test]
+    fn run_diff_jobs_aborts_the_whole_run_on_a_single_failure() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output_dir = tmp.path().join("output");
+        std::fs::create_dir_all(&output_dir).unwrap();
+
+        let good_new = output_dir.join("good.bin");
+        std::fs::write(&good_new, b"new-good").unwrap();
+        let good_old = tmp.path().join("good-old.bin");
+        std::fs::write(&good_old, b"old-good").unwrap();
+
+        let conf = test_conf(EnvConfig {
+            input_dir: tmp.path().to_path_buf(),
+            previous_dir: tmp.path().join("previous"),
+            output_dir: output_dir.clone(),
+        });
+        let generator = Generator::init(&conf, true);
+
+        let jobs = vec![
+            DiffJob {
+                rel_path: "good.bin".to_string(),
+                old_path: good_old,
+                new_path: good_new,
+                from_version: "1.0.0".to_string(),
+            },
+            DiffJob {
+                rel_path: "broken.bin".to_string(),
+                old_path: tmp.path().join("nonexistent-old.bin"),
+                new_path: output_dir.join("nonexistent-new.bin"),
+                from_version: "1.0.0".to_string(),
+            },
+        ];
+
+        let result = generator.run_diff_jobs(jobs);
+        assert!(result.is_err(), "a single failing job should fail the whole batch");
+        let message = format!("{:#}", result.unwrap_err());
+        assert!(message.contains("broken.bin"));
+    }
+}
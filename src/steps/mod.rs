@@ -0,0 +1,6 @@
+pub mod checksums;
+pub mod fetch;
+pub mod generate;
+pub mod package;
+pub mod post;
+pub mod prepare;
@@ -0,0 +1,111 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use log::info;
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use crate::models::config::{Config, NsisCompressionMethod, ZipCompressionMethod};
+use crate::models::manifest::Manifest;
+use crate::utils::progress::Progress;
+
+/// Produces the final release artifacts: the NSIS installer, ZIP archives,
+/// and the finalised update manifest (signing is left to `Signer`).
+pub struct Packaging<'a> {
+    conf: &'a Config,
+}
+
+impl<'a> Packaging<'a> {
+    pub fn init(conf: &'a Config) -> Self {
+        Self { conf }
+    }
+
+    /// Build the NSIS installer from the staged output directory.
+    pub fn run_nsis(&self) -> Result<()> {
+        let script = self.conf.env.output_dir.join("installer.nsi");
+        let compressor = match self.conf.package.installer.compression.method {
+            NsisCompressionMethod::Zlib => "zlib",
+            NsisCompressionMethod::Bzip2 => "bzip2",
+            NsisCompressionMethod::Lzma => "lzma",
+        };
+
+        let status = Command::new("makensis")
+            .arg(format!("/DCOMPRESSOR={compressor}"))
+            .arg(&script)
+            .status()
+            .context("Failed to invoke makensis")?;
+
+        if !status.success() {
+            anyhow::bail!("makensis exited with status {status}");
+        }
+
+        Ok(())
+    }
+
+    /// ZIP up the staged output directory (and PDBs, if present).
+    pub fn create_zips(&self) -> Result<()> {
+        let zip_path = self.conf.env.output_dir.join("release.zip");
+        let file = File::create(&zip_path)
+            .with_context(|| format!("Failed to create {}", zip_path.display()))?;
+        let mut zip = ZipWriter::new(file);
+        let options = Self::zip_options(&self.conf.package.zip.compression);
+
+        let entries: Vec<_> = WalkDir::new(&self.conf.env.output_dir)
+            .into_iter()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context("Failed to walk output directory")?;
+        let file_count = entries.iter().filter(|e| e.file_type().is_file()).count();
+        let progress = Progress::new(self.conf.general.progress, file_count as u64, "Compressing");
+
+        for entry in entries {
+            let rel = entry
+                .path()
+                .strip_prefix(&self.conf.env.output_dir)
+                .expect("walked entry must be under output_dir");
+
+            if entry.file_type().is_file() {
+                zip.start_file(rel.to_string_lossy(), options)
+                    .with_context(|| format!("Failed to add {} to zip", rel.display()))?;
+                let data = std::fs::read(entry.path())
+                    .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+                zip.write_all(&data)?;
+                progress.inc(1);
+            }
+        }
+        progress.finish();
+
+        zip.finish().context("Failed to finalise zip file")?;
+        info!("Wrote {}", zip_path.display());
+        Ok(())
+    }
+
+    /// Serialise and write out the update manifest, returning its path.
+    pub fn finalise_manifest(&self, manifest: &mut Manifest) -> Result<PathBuf> {
+        manifest.sort();
+
+        let manifest_path = self.conf.env.output_dir.join("manifest.json");
+        let data = serde_json::to_vec_pretty(manifest).context("Failed to serialise manifest")?;
+        std::fs::write(&manifest_path, data)
+            .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+
+        Ok(manifest_path)
+    }
+
+    fn zip_options(conf: &crate::models::config::CompressionConfig) -> FileOptions {
+        let method = match conf.method {
+            ZipCompressionMethod::Store => zip::CompressionMethod::Stored,
+            ZipCompressionMethod::Deflate => zip::CompressionMethod::Deflated,
+            ZipCompressionMethod::Zstd => zip::CompressionMethod::Zstd,
+        };
+
+        let mut options = FileOptions::default().compression_method(method);
+        if let Some(level) = conf.level {
+            options = options.compression_level(Some(level));
+        }
+        options
+    }
+}
@@ -0,0 +1,39 @@
+use anyhow::{Context, Result};
+use log::debug;
+use walkdir::WalkDir;
+
+use crate::models::config::Config;
+
+/// Copies the freshly built install dir and PDBs into
+/// `previous_dir/<version>` so the next release can diff against it.
+pub fn copy_to_old(conf: &Config) -> Result<()> {
+    // `Generator` treats each subdirectory of `previous_dir` as one version,
+    // named after `output_dir` like the manifest version.
+    let version = conf
+        .env
+        .output_dir
+        .file_name()
+        .context("Output dir has no name to use as the backup version")?;
+    let backup_dir = conf.env.previous_dir.join(version);
+
+    for entry in WalkDir::new(&conf.env.output_dir) {
+        let entry = entry.context("Failed to walk output directory")?;
+        let rel = entry
+            .path()
+            .strip_prefix(&conf.env.output_dir)
+            .expect("walked entry must be under output_dir");
+        let dest = backup_dir.join(rel);
+
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&dest)
+                .with_context(|| format!("Failed to create dir: {}", dest.display()))?;
+        } else {
+            debug!("Backing up {} -> {}", entry.path().display(), dest.display());
+            std::fs::copy(entry.path(), &dest).with_context(|| {
+                format!("Failed to copy {} to {}", entry.path().display(), dest.display())
+            })?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use ignore::WalkBuilder;
+use log::debug;
+
+use crate::models::config::Config;
+use crate::utils::filter;
+use crate::utils::progress::Progress;
+
+/// Stages the build output into the packaging tree ahead of installer/zip
+/// creation.
+pub struct Preparator<'a> {
+    conf: &'a Config,
+}
+
+impl<'a> Preparator<'a> {
+    pub fn init(conf: &'a Config) -> Self {
+        Self { conf }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let input_dir = &self.conf.env.input_dir;
+        let overrides = filter::build_overrides(input_dir, &self.conf.prepare)?;
+
+        // `git_ignore(false)` etc. so only our own include/exclude patterns
+        // are honored, not the build output's own .gitignore files.
+        let entries = WalkBuilder::new(input_dir)
+            .standard_filters(false)
+            .overrides(overrides)
+            .build()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context("Failed to walk input directory")?;
+
+        let file_count = entries.iter().filter(|e| e.file_type().is_some_and(|t| t.is_file())).count();
+        let progress = Progress::new(self.conf.general.progress, file_count as u64, "Preparing");
+
+        for entry in entries {
+            let rel = entry
+                .path()
+                .strip_prefix(input_dir)
+                .expect("walked entry must be under input_dir");
+            if rel.as_os_str().is_empty() {
+                continue;
+            }
+            let dest = self.conf.env.output_dir.join(rel);
+
+            let file_type = entry
+                .file_type()
+                .with_context(|| format!("Failed to stat {}", entry.path().display()))?;
+            if file_type.is_dir() {
+                fs::create_dir_all(&dest)
+                    .with_context(|| format!("Failed to create dir: {}", dest.display()))?;
+            } else {
+                self.copy_file(entry.path(), &dest)?;
+                progress.inc(1);
+            }
+        }
+
+        progress.finish();
+        Ok(())
+    }
+
+    fn copy_file(&self, src: &Path, dest: &Path) -> Result<()> {
+        debug!("Copying {} -> {}", src.display(), dest.display());
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create dir: {}", parent.display()))?;
+        }
+        fs::copy(src, dest)
+            .with_context(|| format!("Failed to copy {} to {}", src.display(), dest.display()))?;
+        Ok(())
+    }
+}
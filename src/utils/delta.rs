@@ -0,0 +1,9 @@
+use anyhow::{Context, Result};
+
+/// Thin wrapper around `bidiff` so the rest of the crate doesn't need to
+/// know about its translator/writer plumbing.
+pub fn diff(old: &[u8], new: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    bidiff::simple_diff(old, new, &mut out).context("bidiff failed to produce a patch")?;
+    Ok(out)
+}
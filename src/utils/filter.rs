@@ -0,0 +1,77 @@
+use anyhow::{Context, Result};
+use ignore::overrides::{Override, OverrideBuilder};
+
+use crate::models::config::PrepareConfig;
+
+/// Build an `ignore` override set from a `[prepare]` config section.
+///
+/// Patterns in `exclude` are negated so they behave like a normal gitignore
+/// exclude, while `include` patterns are passed through as-is; this matches
+/// `ignore`'s override semantics, which are inverted from plain gitignore.
+pub fn build_overrides(root: &std::path::Path, conf: &PrepareConfig) -> Result<Override> {
+    let mut builder = OverrideBuilder::new(root);
+
+    for pattern in &conf.include {
+        builder
+            .add(pattern)
+            .with_context(|| format!("Invalid prepare.include pattern: {pattern}"))?;
+    }
+    for pattern in &conf.exclude {
+        builder
+            .add(&format!("!{pattern}"))
+            .with_context(|| format!("Invalid prepare.exclude pattern: {pattern}"))?;
+    }
+
+    builder.build().context("Failed to build include/exclude filter")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conf(include: &[&str], exclude: &[&str]) -> PrepareConfig {
+        PrepareConfig {
+            include: include.iter().map(|s| s.to_string()).collect(),
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn empty_include_and_exclude_does_not_touch_anything() {
+        let tmp = tempfile::tempdir().unwrap();
+        let overrides = build_overrides(tmp.path(), &conf(&[], &[])).unwrap();
+
+        // With no patterns at all, nothing should be force-included or
+        // force-excluded: "empty include" must mean "include everything",
+        // not silently invert to "include nothing".
+        assert!(overrides.matched("anything.bin", false).is_none());
+        assert!(overrides.matched("nested/dir/file.txt", false).is_none());
+    }
+
+    #[test]
+    fn include_only_whitelists_matching_paths_and_ignores_the_rest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let overrides = build_overrides(tmp.path(), &conf(&["*.txt"], &[])).unwrap();
+
+        assert!(overrides.matched("notes.txt", false).is_whitelist());
+        assert!(!overrides.matched("image.png", false).is_whitelist());
+    }
+
+    #[test]
+    fn exclude_only_ignores_matching_paths_and_leaves_the_rest_untouched() {
+        let tmp = tempfile::tempdir().unwrap();
+        let overrides = build_overrides(tmp.path(), &conf(&[], &["*.log"])).unwrap();
+
+        assert!(overrides.matched("debug.log", false).is_ignore());
+        assert!(overrides.matched("keep.txt", false).is_none());
+    }
+
+    #[test]
+    fn exclude_wins_over_include_for_the_same_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let overrides = build_overrides(tmp.path(), &conf(&["*.bin"], &["secret.bin"])).unwrap();
+
+        assert!(overrides.matched("secret.bin", false).is_ignore());
+        assert!(overrides.matched("other.bin", false).is_whitelist());
+    }
+}
@@ -0,0 +1,8 @@
+use env_logger::Env;
+
+/// Initialise the global logger at the given level (e.g. "info", "trace").
+pub fn init_logger(level: &str) {
+    env_logger::Builder::from_env(Env::default().default_filter_or(level))
+        .format_timestamp_secs()
+        .init();
+}
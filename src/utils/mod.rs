@@ -0,0 +1,5 @@
+pub mod delta;
+pub mod filter;
+pub mod logging;
+pub mod progress;
+pub mod sign;
@@ -0,0 +1,81 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use indicatif::{ProgressBar, ProgressStyle};
+use log::info;
+
+const LOG_THROTTLE: Duration = Duration::from_secs(1);
+
+/// Reports progress for a fixed-size unit of work (files copied, patches
+/// generated, entries compressed, ...). On an interactive terminal this
+/// drives an indicatif bar; otherwise it falls back to throttled
+/// "N/M done" log lines so CI output stays readable. Safe to share across
+/// rayon workers: the counter is atomic, the indicatif bar is thread-safe,
+/// and log lines are written under a mutex, at most one per throttle window
+/// plus one when the total is reached.
+pub struct Progress {
+    label: String,
+    total: u64,
+    enabled: bool,
+    count: AtomicU64,
+    bar: Option<ProgressBar>,
+    last_logged: Mutex<Instant>,
+}
+
+impl Progress {
+    pub fn new(enabled: bool, total: u64, label: impl Into<String>) -> Self {
+        let label = label.into();
+        let interactive = enabled && std::io::stderr().is_terminal();
+
+        let bar = interactive.then(|| {
+            let bar = ProgressBar::new(total);
+            bar.set_style(
+                ProgressStyle::with_template("{msg} [{bar:40}] {pos}/{len}")
+                    .expect("static progress template is valid")
+                    .progress_chars("=> "),
+            );
+            bar.set_message(label.clone());
+            bar
+        });
+
+        Self {
+            label,
+            total,
+            enabled,
+            count: AtomicU64::new(0),
+            bar,
+            last_logged: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Record that `delta` more units of work completed.
+    pub fn inc(&self, delta: u64) {
+        let done = self.count.fetch_add(delta, Ordering::Relaxed) + delta;
+
+        if let Some(bar) = &self.bar {
+            bar.inc(delta);
+            return;
+        }
+
+        if !self.enabled {
+            return;
+        }
+
+        let mut last_logged = self.last_logged.lock().expect("progress mutex poisoned");
+        if done >= self.total || last_logged.elapsed() >= LOG_THROTTLE {
+            info!("{}: {done}/{} done", self.label, self.total);
+            *last_logged = Instant::now();
+        }
+    }
+
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_with_message(format!("{} done", self.label));
+        } else if self.enabled {
+            let done = self.count.load(Ordering::Relaxed);
+            info!("{}: {done}/{} done", self.label, self.total);
+        }
+    }
+}
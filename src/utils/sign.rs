@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use minisign::{SecretKey, SecretKeyBox};
+
+/// Signs files with a minisign keypair for the updater to verify.
+pub struct Signer {
+    secret_key: Option<SecretKey>,
+}
+
+impl Signer {
+    /// Load the private key from `private_key`, if configured.
+    pub fn init(private_key: Option<&PathBuf>) -> Self {
+        let secret_key = private_key.and_then(|path| {
+            let data = std::fs::read_to_string(path).ok()?;
+            SecretKeyBox::from_string(data.trim())
+                .ok()?
+                .into_secret_key(None)
+                .ok()
+        });
+
+        Self { secret_key }
+    }
+
+    /// Sign `path`, writing the signature to `<path>.minisig`.
+    pub fn sign_file(&mut self, path: &Path) -> Result<()> {
+        let Some(secret_key) = &self.secret_key else {
+            anyhow::bail!("No private key configured, cannot sign {}", path.display());
+        };
+
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read file to sign: {}", path.display()))?;
+        let signature = minisign::sign(None, secret_key, &data[..], None, None)
+            .context("Failed to create signature")?;
+
+        // Append rather than replace the extension: `with_extension` would
+        // turn `manifest.json` into `manifest.minisig`.
+        let sig_path = PathBuf::from(format!("{}.minisig", path.display()));
+        std::fs::write(&sig_path, signature.to_string())
+            .with_context(|| format!("Failed to write signature: {}", sig_path.display()))?;
+
+        Ok(())
+    }
+}